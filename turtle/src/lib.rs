@@ -0,0 +1,12 @@
+//! Streaming parsers for line-oriented RDF serializations (N-Triples, N-Quads).
+
+mod error;
+mod shared;
+mod utils;
+
+pub mod nquads;
+pub mod ntriples;
+
+pub use crate::error::TurtleError;
+pub use crate::nquads::NQuadsParser;
+pub use crate::ntriples::NTriplesParser;
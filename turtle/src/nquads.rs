@@ -0,0 +1,200 @@
+//! Implementation of [N-Quads](https://www.w3.org/TR/n-quads/) RDF syntax
+
+use crate::error::*;
+use crate::ntriples::{parse_iriref, parse_named_or_blank_node, parse_term, skip_until_eol, skip_whitespace};
+use crate::shared::*;
+use rio_api::model::*;
+use rio_api::parser::*;
+use std::io::BufRead;
+
+/// A [N-Quads](https://www.w3.org/TR/n-quads/) streaming parser.
+///
+/// It implements the `QuadParser` trait.
+///
+/// Its memory consumption is linear in the size of the longest line of the file.
+/// It does not do any allocation during parsing except buffer resizing
+/// if a line significantly longer than the previous is encountered.
+///
+///
+/// Count the number of of people using `QuadParser` API:
+/// ```
+/// use rio_turtle::NQuadsParser;
+/// use rio_api::parser::QuadParser;
+/// use rio_api::model::NamedNode;
+///
+/// let file = b"<http://example.com/foo> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://schema.org/Person> <http://example.com/graph> .
+/// <http://example.com/foo> <http://schema.org/name> \"Foo\" <http://example.com/graph> .
+/// <http://example.com/bar> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://schema.org/Person> .
+/// <http://example.com/bar> <http://schema.org/name> \"Bar\" .";
+///
+/// let rdf_type = NamedNode { iri: "http://www.w3.org/1999/02/22-rdf-syntax-ns#type" };
+/// let schema_person = NamedNode { iri: "http://schema.org/Person" };
+/// let mut count = 0;
+/// NQuadsParser::new(file.as_ref()).unwrap().parse_all(&mut |q| {
+///     if q.predicate == rdf_type && q.object == schema_person.into() {
+///         count += 1;
+///     }
+/// }).unwrap();
+/// assert_eq!(2, count)
+/// ```
+pub struct NQuadsParser<R: BufRead> {
+    read: OneLookAheadLineByteReader<R>,
+    subject_buf: Vec<u8>,
+    predicate_buf: Vec<u8>,
+    object_buf: Vec<u8>,
+    object_annotation_buf: Vec<u8>, // datatype or language tag
+    graph_name_buf: Vec<u8>,
+}
+
+impl<R: BufRead> NQuadsParser<R> {
+    pub fn new(reader: R) -> Result<Self, TurtleError> {
+        Ok(Self {
+            read: OneLookAheadLineByteReader::new(reader)?,
+            subject_buf: Vec::default(),
+            predicate_buf: Vec::default(),
+            object_buf: Vec::default(),
+            object_annotation_buf: Vec::default(),
+            graph_name_buf: Vec::default(),
+        })
+    }
+}
+
+impl<R: BufRead> QuadParser for NQuadsParser<R> {
+    type Error = TurtleError;
+
+    fn parse_step(&mut self, on_quad: &mut impl FnMut(Quad) -> ()) -> Result<(), TurtleError> {
+        if let Some(result) = parse_line(
+            &mut self.read,
+            &mut self.subject_buf,
+            &mut self.predicate_buf,
+            &mut self.object_buf,
+            &mut self.object_annotation_buf,
+            &mut self.graph_name_buf,
+        )? {
+            on_quad(result);
+
+            //We clear the buffers
+            self.subject_buf.clear();
+            self.predicate_buf.clear();
+            self.object_buf.clear();
+            self.object_annotation_buf.clear();
+            self.graph_name_buf.clear();
+        }
+        Ok(())
+    }
+
+    fn is_end(&self) -> bool {
+        self.read.current() == EOF
+    }
+}
+
+fn parse_line<'a>(
+    read: &mut impl OneLookAheadLineByteRead,
+    subject_buf: &'a mut Vec<u8>,
+    predicate_buf: &'a mut Vec<u8>,
+    object_buf: &'a mut Vec<u8>,
+    object_annotation_buf: &'a mut Vec<u8>,
+    graph_name_buf: &'a mut Vec<u8>,
+) -> Result<Option<Quad<'a>>, TurtleError> {
+    skip_whitespace(read)?;
+
+    let subject = match read.current() {
+        EOF | b'#' | b'\r' | b'\n' => {
+            skip_until_eol(read)?;
+            return Ok(None);
+        }
+        _ => parse_named_or_blank_node(read, subject_buf)?,
+    };
+
+    skip_whitespace(read)?;
+
+    let predicate = parse_iriref(read, predicate_buf)?;
+
+    skip_whitespace(read)?;
+
+    let object = parse_term(read, object_buf, object_annotation_buf)?;
+
+    skip_whitespace(read)?;
+
+    let graph_name = match read.current() {
+        b'.' => None,
+        _ => Some(parse_named_or_blank_node(read, graph_name_buf)?),
+    };
+
+    skip_whitespace(read)?;
+    read.check_is_current(b'.')?;
+    read.consume()?;
+
+    skip_whitespace(read)?;
+    match read.current() {
+        EOF | b'#' | b'\r' | b'\n' => skip_until_eol(read)?,
+        _ => read.unexpected_char_error()?,
+    }
+
+    Ok(Some(Quad {
+        subject,
+        predicate,
+        object,
+        graph_name,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph_name_str(graph_name: Option<NamedOrBlankNode>) -> Option<String> {
+        graph_name.map(|g| match g {
+            NamedOrBlankNode::NamedNode(n) => n.iri.to_string(),
+            NamedOrBlankNode::BlankNode(b) => format!("_:{}", b.id),
+        })
+    }
+
+    fn parse(input: &str) -> Vec<(String, Option<String>)> {
+        let mut quads = Vec::new();
+        NQuadsParser::new(input.as_bytes())
+            .unwrap()
+            .parse_all(&mut |q| {
+                let subject = match q.subject {
+                    NamedOrBlankNode::NamedNode(n) => n.iri.to_string(),
+                    NamedOrBlankNode::BlankNode(b) => format!("_:{}", b.id),
+                };
+                quads.push((subject, graph_name_str(q.graph_name)))
+            })
+            .unwrap();
+        quads
+    }
+
+    #[test]
+    fn quad_without_graph_name_is_in_the_default_graph() {
+        let quads = parse(
+            "<http://example.com/s> <http://example.com/p> <http://example.com/o> .\n",
+        );
+        assert_eq!(quads, vec![("http://example.com/s".to_string(), None)]);
+    }
+
+    #[test]
+    fn quad_with_blank_node_graph_label_is_parsed() {
+        let quads = parse(
+            "<http://example.com/s> <http://example.com/p> <http://example.com/o> _:g1 .\n",
+        );
+        assert_eq!(
+            quads,
+            vec![("http://example.com/s".to_string(), Some("_:g1".to_string()))]
+        );
+    }
+
+    #[test]
+    fn quad_with_named_graph_is_parsed() {
+        let quads = parse(
+            "<http://example.com/s> <http://example.com/p> <http://example.com/o> <http://example.com/g> .\n",
+        );
+        assert_eq!(
+            quads,
+            vec![(
+                "http://example.com/s".to_string(),
+                Some("http://example.com/g".to_string())
+            )]
+        );
+    }
+}
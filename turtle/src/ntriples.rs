@@ -56,6 +56,56 @@ impl<R: BufRead> NTriplesParser<R> {
             object_annotation_buf: Vec::default(),
         })
     }
+
+    /// RDF-star-aware counterpart of `TripleParser::parse_step`: also recognizes
+    /// `<< subject predicate object >>` quoted triples in subject/object position, returning a
+    /// [`StarTriple`] instead of a plain [`Triple`]. See [`StarTriple`] for why it's a separate
+    /// method rather than a change to `parse_step` itself.
+    ///
+    /// Unlike `parse_step`, this allocates: each quoted triple is built from owned nodes
+    /// (see [`QuotedTriple`]) instead of living in the parser's reused buffers.
+    ///
+    /// ```
+    /// use rio_turtle::ntriples::{NTriplesParser, StarSubject};
+    ///
+    /// let file = b"<< <http://example.com/foo> <http://schema.org/says> \"hi\" >> <http://schema.org/certainty> \"0.8\" .";
+    /// let mut quoted = 0;
+    /// NTriplesParser::new(file.as_ref()).unwrap().parse_all_star(|t| {
+    ///     if let StarSubject::Triple(_) = t.subject {
+    ///         quoted += 1;
+    ///     }
+    /// }).unwrap();
+    /// assert_eq!(1, quoted);
+    /// ```
+    pub fn parse_step_star(
+        &mut self,
+        on_triple: &mut impl FnMut(StarTriple),
+    ) -> Result<(), TurtleError> {
+        if let Some(result) = parse_line_star(
+            &mut self.read,
+            &mut self.subject_buf,
+            &mut self.predicate_buf,
+            &mut self.object_buf,
+            &mut self.object_annotation_buf,
+        )? {
+            on_triple(result);
+
+            //We clear the buffers
+            self.subject_buf.clear();
+            self.predicate_buf.clear();
+            self.object_buf.clear();
+            self.object_annotation_buf.clear();
+        }
+        Ok(())
+    }
+
+    /// Calls [`parse_step_star`](Self::parse_step_star) until the input is exhausted.
+    pub fn parse_all_star(&mut self, mut on_triple: impl FnMut(StarTriple)) -> Result<(), TurtleError> {
+        while !self.is_end() {
+            self.parse_step_star(&mut on_triple)?;
+        }
+        Ok(())
+    }
 }
 
 impl<R: BufRead> TripleParser for NTriplesParser<R> {
@@ -85,7 +135,7 @@ impl<R: BufRead> TripleParser for NTriplesParser<R> {
     }
 }
 
-fn parse_line<'a>(
+pub(crate) fn parse_line<'a>(
     read: &mut impl OneLookAheadLineByteRead,
     subject_buf: &'a mut Vec<u8>,
     predicate_buf: &'a mut Vec<u8>,
@@ -127,30 +177,298 @@ fn parse_line<'a>(
     }))
 }
 
-fn parse_term<'a>(
+pub(crate) fn parse_term<'a>(
     read: &mut impl OneLookAheadLineByteRead,
     buffer: &'a mut Vec<u8>,
     annotation_buffer: &'a mut Vec<u8>,
 ) -> Result<Term<'a>, TurtleError> {
     match read.current() {
-        b'<' => Ok(parse_iriref(read, buffer)?.into()),
+        b'<' => Ok(match parse_angle_bracketed(read, buffer)? {
+            AngleBracketed::IriRef(iri) => iri.into(),
+            AngleBracketed::QuotedTriple(_) => {
+                return read.unexpected_char_error();
+            }
+        }),
         b'_' => Ok(parse_blank_node_label(read, buffer)?.into()),
         b'"' => Ok(parse_literal(read, buffer, annotation_buffer)?.into()),
         _ => read.unexpected_char_error(),
     }
 }
 
-fn parse_named_or_blank_node<'a>(
+pub(crate) fn parse_named_or_blank_node<'a>(
     read: &mut impl OneLookAheadLineByteRead,
     buffer: &'a mut Vec<u8>,
 ) -> Result<NamedOrBlankNode<'a>, TurtleError> {
     match read.current() {
-        b'<' => Ok(parse_iriref(read, buffer)?.into()),
+        b'<' => Ok(match parse_angle_bracketed(read, buffer)? {
+            AngleBracketed::IriRef(iri) => iri.into(),
+            AngleBracketed::QuotedTriple(_) => {
+                return read.unexpected_char_error();
+            }
+        }),
         b'_' => Ok(parse_blank_node_label(read, buffer)?.into()),
         _ => read.unexpected_char_error(),
     }
 }
 
+/// Subject of a [`StarTriple`]: anything [`parse_named_or_blank_node`] accepts, plus –
+/// recursively – a nested quoted triple.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StarSubject<'a> {
+    NamedNode(NamedNode<'a>),
+    BlankNode(BlankNode<'a>),
+    Triple(Box<QuotedTriple>),
+}
+
+/// Object of a [`StarTriple`]: anything [`parse_term`] accepts, plus – recursively – a
+/// nested quoted triple.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StarTerm<'a> {
+    NamedNode(NamedNode<'a>),
+    BlankNode(BlankNode<'a>),
+    Literal(Literal<'a>),
+    Triple(Box<QuotedTriple>),
+}
+
+/// A statement produced by [`NTriplesParser::parse_step_star`]/[`NTriplesParser::parse_all_star`].
+///
+/// `rio_api::model::Term`/`NamedOrBlankNode` have no quoted-triple variant, so a line that
+/// embeds one (`<< s p o >> p2 o2 .`) cannot be represented as a plain [`Triple`] – that's why
+/// this lives next to, rather than inside, the ordinary `TripleParser` impl above.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StarTriple<'a> {
+    pub subject: StarSubject<'a>,
+    pub predicate: NamedNode<'a>,
+    pub object: StarTerm<'a>,
+}
+
+fn parse_term_star<'a>(
+    read: &mut impl OneLookAheadLineByteRead,
+    buffer: &'a mut Vec<u8>,
+    annotation_buffer: &'a mut Vec<u8>,
+) -> Result<StarTerm<'a>, TurtleError> {
+    match read.current() {
+        b'<' => Ok(match parse_angle_bracketed(read, buffer)? {
+            AngleBracketed::IriRef(iri) => StarTerm::NamedNode(iri),
+            AngleBracketed::QuotedTriple(triple) => StarTerm::Triple(Box::new(triple)),
+        }),
+        b'_' => Ok(StarTerm::BlankNode(parse_blank_node_label(read, buffer)?)),
+        b'"' => Ok(StarTerm::Literal(parse_literal(read, buffer, annotation_buffer)?)),
+        _ => read.unexpected_char_error(),
+    }
+}
+
+fn parse_named_or_blank_node_star<'a>(
+    read: &mut impl OneLookAheadLineByteRead,
+    buffer: &'a mut Vec<u8>,
+) -> Result<StarSubject<'a>, TurtleError> {
+    match read.current() {
+        b'<' => Ok(match parse_angle_bracketed(read, buffer)? {
+            AngleBracketed::IriRef(iri) => StarSubject::NamedNode(iri),
+            AngleBracketed::QuotedTriple(triple) => StarSubject::Triple(Box::new(triple)),
+        }),
+        b'_' => Ok(StarSubject::BlankNode(parse_blank_node_label(read, buffer)?)),
+        _ => read.unexpected_char_error(),
+    }
+}
+
+fn parse_line_star<'a>(
+    read: &mut impl OneLookAheadLineByteRead,
+    subject_buf: &'a mut Vec<u8>,
+    predicate_buf: &'a mut Vec<u8>,
+    object_buf: &'a mut Vec<u8>,
+    object_annotation_buf: &'a mut Vec<u8>,
+) -> Result<Option<StarTriple<'a>>, TurtleError> {
+    skip_whitespace(read)?;
+
+    let subject = match read.current() {
+        EOF | b'#' | b'\r' | b'\n' => {
+            skip_until_eol(read)?;
+            return Ok(None);
+        }
+        _ => parse_named_or_blank_node_star(read, subject_buf)?,
+    };
+
+    skip_whitespace(read)?;
+    let predicate = parse_iriref(read, predicate_buf)?;
+
+    skip_whitespace(read)?;
+    let object = parse_term_star(read, object_buf, object_annotation_buf)?;
+
+    skip_whitespace(read)?;
+    read.check_is_current(b'.')?;
+    read.consume()?;
+
+    skip_whitespace(read)?;
+    match read.current() {
+        EOF | b'#' | b'\r' | b'\n' => skip_until_eol(read)?,
+        _ => read.unexpected_char_error()?,
+    }
+
+    Ok(Some(StarTriple {
+        subject,
+        predicate,
+        object,
+    }))
+}
+
+/// What follows a leading `<`: either a plain `IRIREF`, or – if the `<` is immediately
+/// doubled – an RDF-star quoted triple `<< subject predicate object >>`.
+enum AngleBracketed<'a> {
+    IriRef(NamedNode<'a>),
+    QuotedTriple(QuotedTriple),
+}
+
+fn parse_angle_bracketed<'a>(
+    read: &mut impl OneLookAheadLineByteRead,
+    buffer: &'a mut Vec<u8>,
+) -> Result<AngleBracketed<'a>, TurtleError> {
+    read.check_is_current(b'<')?;
+    read.consume()?;
+    if read.current() == b'<' {
+        read.consume()?;
+        let triple = parse_quoted_triple(read)?;
+        skip_whitespace(read)?;
+        read.check_is_current(b'>')?;
+        read.consume()?;
+        read.check_is_current(b'>')?;
+        read.consume()?;
+        Ok(AngleBracketed::QuotedTriple(triple))
+    } else {
+        parse_iriref_tail(read, buffer)?;
+        Ok(AngleBracketed::IriRef(NamedNode {
+            iri: to_str(read, buffer)?,
+        }))
+    }
+}
+
+/// Parses the remainder of an `IRIREF` whose leading `<` has already been consumed by a
+/// `<<` look-ahead, pushing bytes into `buffer` and consuming the closing `>`.
+fn parse_iriref_tail(
+    read: &mut impl OneLookAheadLineByteRead,
+    buffer: &mut Vec<u8>,
+) -> Result<(), TurtleError> {
+    loop {
+        match read.current() {
+            b'>' => {
+                read.consume()?;
+                return Ok(());
+            }
+            EOF | b'\n' | b'\r' => return read.unexpected_char_error(),
+            c => {
+                buffer.push(c);
+                read.consume()?;
+            }
+        }
+    }
+}
+
+/// An RDF-star quoted triple.
+///
+/// Unlike the top-level [`Triple`] handed to `on_triple`, a quoted triple's components are
+/// owned: nesting means each `<< ... >>` allocates its own nodes as it is parsed rather than
+/// reusing `NTriplesParser`'s four flat buffers, which cannot hold more than one statement's
+/// worth of borrowed data at a time.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuotedTriple {
+    pub subject: QuotedSubject,
+    pub predicate: String,
+    pub object: QuotedTerm,
+}
+
+/// The subject of a [`QuotedTriple`]: a named node, a blank node, or – recursively –
+/// another quoted triple.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum QuotedSubject {
+    NamedNode(String),
+    BlankNode(String),
+    Triple(Box<QuotedTriple>),
+}
+
+/// The object of a [`QuotedTriple`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum QuotedTerm {
+    NamedNode(String),
+    BlankNode(String),
+    Literal(QuotedLiteral),
+    Triple(Box<QuotedTriple>),
+}
+
+/// An owned counterpart of [`Literal`] for use inside a [`QuotedTriple`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum QuotedLiteral {
+    Simple(String),
+    LanguageTaggedString(String, String),
+    Typed(String, String),
+}
+
+/// Parses the body of an RDF-star quoted triple once both bytes of the opening `<<` have been
+/// consumed, up to (but not including) the closing `>>`.
+fn parse_quoted_triple(read: &mut impl OneLookAheadLineByteRead) -> Result<QuotedTriple, TurtleError> {
+    skip_whitespace(read)?;
+    let mut subject_buf = Vec::default();
+    let subject = parse_quoted_subject(read, &mut subject_buf)?;
+
+    skip_whitespace(read)?;
+    let mut predicate_buf = Vec::default();
+    let predicate = parse_iriref(read, &mut predicate_buf)?.iri.to_string();
+
+    skip_whitespace(read)?;
+    let mut object_buf = Vec::default();
+    let mut object_annotation_buf = Vec::default();
+    let object = parse_quoted_term(read, &mut object_buf, &mut object_annotation_buf)?;
+
+    Ok(QuotedTriple {
+        subject,
+        predicate,
+        object,
+    })
+}
+
+fn parse_quoted_subject<'a>(
+    read: &mut impl OneLookAheadLineByteRead,
+    buffer: &'a mut Vec<u8>,
+) -> Result<QuotedSubject, TurtleError> {
+    match read.current() {
+        b'<' => match parse_angle_bracketed(read, buffer)? {
+            AngleBracketed::IriRef(iri) => Ok(QuotedSubject::NamedNode(iri.iri.to_string())),
+            AngleBracketed::QuotedTriple(triple) => Ok(QuotedSubject::Triple(Box::new(triple))),
+        },
+        b'_' => Ok(QuotedSubject::BlankNode(
+            parse_blank_node_label(read, buffer)?.id.to_string(),
+        )),
+        _ => read.unexpected_char_error(),
+    }
+}
+
+fn parse_quoted_term<'a>(
+    read: &mut impl OneLookAheadLineByteRead,
+    buffer: &'a mut Vec<u8>,
+    annotation_buffer: &'a mut Vec<u8>,
+) -> Result<QuotedTerm, TurtleError> {
+    match read.current() {
+        b'<' => match parse_angle_bracketed(read, buffer)? {
+            AngleBracketed::IriRef(iri) => Ok(QuotedTerm::NamedNode(iri.iri.to_string())),
+            AngleBracketed::QuotedTriple(triple) => Ok(QuotedTerm::Triple(Box::new(triple))),
+        },
+        b'_' => Ok(QuotedTerm::BlankNode(
+            parse_blank_node_label(read, buffer)?.id.to_string(),
+        )),
+        b'"' => Ok(QuotedTerm::Literal(
+            match parse_literal(read, buffer, annotation_buffer)? {
+                Literal::Simple { value } => QuotedLiteral::Simple(value.to_string()),
+                Literal::LanguageTaggedString { value, language } => {
+                    QuotedLiteral::LanguageTaggedString(value.to_string(), language.to_string())
+                }
+                Literal::Typed { value, datatype } => {
+                    QuotedLiteral::Typed(value.to_string(), datatype.iri.to_string())
+                }
+            },
+        )),
+        _ => read.unexpected_char_error(),
+    }
+}
+
 fn parse_literal<'a>(
     read: &mut impl OneLookAheadLineByteRead,
     buffer: &'a mut Vec<u8>,
@@ -183,7 +501,7 @@ fn parse_literal<'a>(
     }
 }
 
-fn skip_whitespace(read: &mut impl OneLookAheadLineByteRead) -> Result<(), TurtleError> {
+pub(crate) fn skip_whitespace(read: &mut impl OneLookAheadLineByteRead) -> Result<(), TurtleError> {
     loop {
         match read.current() {
             b' ' | b'\t' => read.consume()?,
@@ -192,7 +510,7 @@ fn skip_whitespace(read: &mut impl OneLookAheadLineByteRead) -> Result<(), Turtl
     }
 }
 
-fn skip_until_eol(read: &mut impl OneLookAheadLineByteRead) -> Result<(), TurtleError> {
+pub(crate) fn skip_until_eol(read: &mut impl OneLookAheadLineByteRead) -> Result<(), TurtleError> {
     loop {
         match read.current() {
             EOF => return Ok(()),
@@ -206,7 +524,7 @@ fn skip_until_eol(read: &mut impl OneLookAheadLineByteRead) -> Result<(), Turtle
     }
 }
 
-fn parse_iriref<'a>(
+pub(crate) fn parse_iriref<'a>(
     read: &mut impl OneLookAheadLineByteRead,
     buffer: &'a mut Vec<u8>,
 ) -> Result<NamedNode<'a>, TurtleError> {
@@ -215,3 +533,124 @@ fn parse_iriref<'a>(
         iri: to_str(read, buffer)?,
     })
 }
+
+/// Async (tokio) counterpart of [`NTriplesParser`], gated behind the `async-tokio` feature so
+/// synchronous users don't pull in tokio at all.
+///
+/// This checkout has no `Cargo.toml` for the `turtle` crate, so the `async-tokio` feature and
+/// its optional `tokio` dependency aren't declared anywhere; until they are, this module can
+/// never actually be compiled in. Wiring it up needs, in the crate manifest:
+/// ```toml
+/// [features]
+/// async-tokio = ["dep:tokio"]
+///
+/// [dependencies]
+/// tokio = { version = "1", features = ["io-util"], optional = true }
+/// ```
+#[cfg(feature = "async-tokio")]
+pub mod async_io {
+    use super::*;
+    use std::io;
+    use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+    /// An [`NTriplesParser`] that drives a `tokio::io::AsyncBufRead` instead of a blocking
+    /// `std::io::BufRead`.
+    ///
+    /// N-Triples is line-oriented, so only reading a line needs to await the underlying
+    /// reader; once a line is in memory it is parsed with the exact same `parse_line` the
+    /// synchronous parser uses, via [`LineSliceReader`], so the two stay behaviorally
+    /// identical.
+    pub struct AsyncNTriplesParser<R> {
+        read: R,
+        line_buf: Vec<u8>,
+        subject_buf: Vec<u8>,
+        predicate_buf: Vec<u8>,
+        object_buf: Vec<u8>,
+        object_annotation_buf: Vec<u8>,
+    }
+
+    impl<R: AsyncBufRead + Unpin> AsyncNTriplesParser<R> {
+        pub fn new(read: R) -> Self {
+            Self {
+                read,
+                line_buf: Vec::default(),
+                subject_buf: Vec::default(),
+                predicate_buf: Vec::default(),
+                object_buf: Vec::default(),
+                object_annotation_buf: Vec::default(),
+            }
+        }
+
+        /// Reads and parses statements, awaiting the reader between lines, until it reaches
+        /// EOF, invoking `on_triple` for each statement parsed.
+        pub async fn parse_all(
+            &mut self,
+            mut on_triple: impl FnMut(Triple),
+        ) -> Result<(), TurtleError> {
+            loop {
+                self.line_buf.clear();
+                if self.read.read_until(b'\n', &mut self.line_buf).await? == 0 {
+                    return Ok(());
+                }
+
+                let mut line_read = LineSliceReader::new(&self.line_buf);
+                if let Some(triple) = parse_line(
+                    &mut line_read,
+                    &mut self.subject_buf,
+                    &mut self.predicate_buf,
+                    &mut self.object_buf,
+                    &mut self.object_annotation_buf,
+                )? {
+                    on_triple(triple);
+                }
+
+                self.subject_buf.clear();
+                self.predicate_buf.clear();
+                self.object_buf.clear();
+                self.object_annotation_buf.clear();
+            }
+        }
+    }
+
+    /// A `OneLookAheadLineByteRead` over a line already buffered in memory.
+    ///
+    /// This is what lets [`AsyncNTriplesParser`] reuse the synchronous, allocation-free
+    /// parsing functions: once tokio has filled a whole line, scanning it no longer touches
+    /// the reader, so it needs no lookahead of its own beyond indexing into the slice.
+    struct LineSliceReader<'a> {
+        line: &'a [u8],
+        position: usize,
+    }
+
+    impl<'a> LineSliceReader<'a> {
+        fn new(line: &'a [u8]) -> Self {
+            Self { line, position: 0 }
+        }
+    }
+
+    impl<'a> OneLookAheadLineByteRead for LineSliceReader<'a> {
+        fn current(&self) -> u8 {
+            self.line.get(self.position).copied().unwrap_or(EOF)
+        }
+
+        fn consume(&mut self) -> Result<(), TurtleError> {
+            self.position += 1;
+            Ok(())
+        }
+
+        fn check_is_current(&self, expected: u8) -> Result<(), TurtleError> {
+            if self.current() == expected {
+                Ok(())
+            } else {
+                self.unexpected_char_error()
+            }
+        }
+
+        fn unexpected_char_error<T>(&self) -> Result<T, TurtleError> {
+            Err(TurtleError::from(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Unexpected byte {:?} found", self.current()),
+            )))
+        }
+    }
+}
@@ -1,7 +1,7 @@
-use std::{collections::HashMap, io::{self, Write}};
+use std::{collections::{HashMap, HashSet}, io::{self, Write}};
 
 use quick_xml::{Writer, events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event}};
-use rio_api::model::{BlankNode, Literal, NamedNode, NamedOrBlankNode, Term, Triple};
+use rio_api::model::{BlankNode, Literal, NamedNode, NamedOrBlankNode, Quad, Term, Triple};
 
 use crate::utils::{is_name_char, is_name_start_char};
 
@@ -68,6 +68,12 @@ impl From<Literal<'_>> for AsRefLiteral<String> {
 pub enum AsRefNamedOrBlankNode<A:AsRef<str>> {
     NamedNode(AsRefNamedNode<A>),
     BlankNode(AsRefBlankNode<A>),
+    /// An RDF-star quoted triple standing in for its own subject.
+    ///
+    /// Reached only by building an `AsRefQuotedTriple` directly, e.g. from a parser like
+    /// `rio_turtle::NTriplesParser::parse_step_star`; `From<NamedOrBlankNode<'_>>` never
+    /// produces it, since that type predates RDF-star and has no such variant.
+    Triple(Box<AsRefQuotedTriple<A>>),
 }
 
 impl From<NamedOrBlankNode<'_>> for AsRefNamedOrBlankNode<String> {
@@ -86,6 +92,9 @@ pub enum AsRefTerm<A:AsRef<str>> {
     NamedNode(AsRefNamedNode<A>),
     BlankNode(AsRefBlankNode<A>),
     Literal(AsRefLiteral<A>),
+    /// An RDF-star quoted triple standing in for its own object. See
+    /// [`AsRefNamedOrBlankNode::Triple`] for why there's no `From<Term<'_>>` arm for it.
+    Triple(Box<AsRefQuotedTriple<A>>),
 }
 
 impl From<Term<'_>> for AsRefTerm<String> {
@@ -101,6 +110,19 @@ impl From<Term<'_>> for AsRefTerm<String> {
     }
 }
 
+/// An RDF-star quoted triple, owned and `AsRef<str>`-generic like the rest of this module's
+/// `AsRef*` family. Mirrors `rio_turtle::ntriples::QuotedTriple`'s shape, so a caller
+/// holding one can rebuild it as an `AsRefQuotedTriple` and wrap it in
+/// [`AsRefTerm::Triple`]/[`AsRefNamedOrBlankNode::Triple`].
+/// [`AbbrevRdfXmlFormatter::format`]/[`AbbrevRdfXmlFormatter::format_quad`] do not serialize
+/// such a statement: they return an `io::ErrorKind::Unsupported` error for it.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Clone, Hash)]
+pub struct AsRefQuotedTriple<A: AsRef<str>> {
+    pub subject: AsRefNamedOrBlankNode<A>,
+    pub predicate: AsRefNamedNode<A>,
+    pub object: AsRefTerm<A>,
+}
+
 #[derive(Debug)]
 pub struct AsRefTriple<A: AsRef<str>> {
     pub subject: AsRefNamedOrBlankNode<A>,
@@ -118,12 +140,32 @@ impl From<Triple<'_>> for AsRefTriple<String> {
     }
 }
 
+#[derive(Debug)]
+pub struct AsRefQuad<A: AsRef<str>> {
+    pub subject: AsRefNamedOrBlankNode<A>,
+    pub predicate: AsRefNamedNode<A>,
+    pub object: AsRefTerm<A>,
+    pub graph_name: Option<AsRefNamedOrBlankNode<A>>,
+}
+
+impl From<Quad<'_>> for AsRefQuad<String> {
+    fn from(q: Quad<'_>) -> Self {
+        AsRefQuad {
+            subject: q.subject.into(),
+            predicate: q.predicate.into(),
+            object: q.object.into(),
+            graph_name: q.graph_name.map(Into::into),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct AbbrevRdfXmlFormatterConfig {
     pub bnode_contract: bool,
     pub indentation: usize,
     pub prefix: HashMap<String, String>,
-    pub typed_node: bool
+    pub typed_node: bool,
+    pub base: Option<String>,
 }
 
 impl AbbrevRdfXmlFormatterConfig {
@@ -132,7 +174,8 @@ impl AbbrevRdfXmlFormatterConfig {
             bnode_contract: false,
             indentation: 0,
             prefix: HashMap::new(),
-            typed_node: false
+            typed_node: false,
+            base: None,
         }
     }
 }
@@ -148,10 +191,15 @@ pub struct AbbrevRdfXmlFormatter<A:AsRef<str>, W: Write> {
     current_subject: Vec<AsRefNamedOrBlankNode<A>>,
     current_close: Vec<Vec<u8>>,
     maybe_empty_open: Option<BytesStart<'static>>,
+    current_graph: Option<AsRefNamedOrBlankNode<A>>,
+    // Only populated when `config.bnode_contract` is set: striping a blank node into its
+    // single referrer needs to see every one of its statements before any byte of the
+    // referrer is written, which the one-triple-at-a-time streaming path below can't offer.
+    pending: Vec<AsRefTriple<A>>,
 }
 
 impl<A, W> AbbrevRdfXmlFormatter<A, W>
-where A: AsRef<str> + Clone + std::fmt::Debug + PartialEq,
+where A: AsRef<str> + Clone + std::fmt::Debug + PartialEq + Eq + std::hash::Hash,
       W: Write,
 {
     /// Builds a new formatter from a `Write` implementation and starts writing
@@ -164,7 +212,9 @@ where A: AsRef<str> + Clone + std::fmt::Debug + PartialEq,
             config,
             current_subject: vec![],
             current_close: vec![],
-            maybe_empty_open: None
+            maybe_empty_open: None,
+            current_graph: None,
+            pending: vec![],
         }
         .write_declaration()
     }
@@ -174,11 +224,40 @@ where A: AsRef<str> + Clone + std::fmt::Debug + PartialEq,
             .map_err(map_err)?;
         let mut rdf_open = BytesStart::borrowed_name(b"rdf:RDF");
         self.write_prefix(&mut rdf_open)?;
+        if let Some(base) = self.config.base.clone() {
+            rdf_open.push_attribute(("xml:base", base.as_str()));
+        }
         self.write_event(Event::Start(rdf_open))
             .map_err(map_err)?;
         Ok(self)
     }
 
+    /// Shortens `iri` against the configured `xml:base`, if any.
+    ///
+    /// The match is purely string-prefix based: the remainder is returned when it is empty or
+    /// starts with `#` or `/`, or when the base itself ends in `#` or `/`. A base with no
+    /// trailing separator whose remainder doesn't start with one is left alone, since
+    /// resolving it as a relative reference would replace the base's last segment instead of
+    /// extending it. No further reference-resolution check is made, so resolving the
+    /// remainder against the base does not always give back `iri`: a base's `#` fragment is
+    /// dropped on resolution, and a remainder starting with `/` replaces any path the base
+    /// has. IRIs that don't match the base are returned untouched.
+    fn relativize_iri<'a>(&self, iri: &'a str) -> &'a str {
+        if let Some(base) = &self.config.base {
+            if let Some(rest) = iri.strip_prefix(base.as_str()) {
+                if rest.is_empty()
+                    || rest.starts_with('#')
+                    || rest.starts_with('/')
+                    || base.ends_with('#')
+                    || base.ends_with('/')
+                {
+                    return rest;
+                }
+            }
+        }
+        iri
+    }
+
     fn write_prefix(&mut self, rdf_open: &mut BytesStart<'_>) -> Result<(), io::Error> {
         for i in &self.config.prefix {
             let ns = format!("xmlns:{}", &i.1);
@@ -225,6 +304,12 @@ where A: AsRef<str> + Clone + std::fmt::Debug + PartialEq,
     fn write_start(&mut self, event: Event<'_>) -> Result<(), quick_xml::Error> {
         match event {
             Event::Start(bs) => {
+                // A `Start` already pending here has a child (this one), so it can't turn out
+                // to be empty; flush it for real instead of letting this call silently
+                // overwrite it and lose its bytes.
+                if let Some(pending) = self.maybe_empty_open.take() {
+                    self.writer.write_event(Event::Start(pending))?;
+                }
                 self.current_close.push(bs.name().to_vec());
                 self.maybe_empty_open = Some(bs.to_owned());
             }
@@ -244,7 +329,51 @@ where A: AsRef<str> + Clone + std::fmt::Debug + PartialEq,
         self.writer.write_event(event)
     }
 
+    /// Formats a quad, grouping consecutive statements sharing a graph the same way `format`
+    /// groups consecutive statements sharing a subject.
+    ///
+    /// RDF/XML itself is graph-unaware, so every graph is serialized the same way; only the
+    /// subject-grouping state is reset on a graph change, giving one description block per
+    /// subject per graph rather than merging runs across graph boundaries.
+    ///
+    /// With `config.bnode_contract` set, quads are buffered like triples and grouped by
+    /// subject alone when [`finish`](Self::finish) flushes them; graph boundaries are not
+    /// kept in that mode.
+    pub fn format_quad(&mut self, quad: &AsRefQuad<A>) -> Result<(), io::Error> {
+        if self.current_graph != quad.graph_name {
+            if !self.current_subject.is_empty() {
+                self.write_close()?;
+            }
+            self.current_subject.clear();
+            self.current_graph = quad.graph_name.clone();
+        }
+
+        self.format(&AsRefTriple {
+            subject: quad.subject.clone(),
+            predicate: quad.predicate.clone(),
+            object: quad.object.clone(),
+        })
+    }
+
+    /// Formats a triple.
+    ///
+    /// When `config.bnode_contract` is set, triples are buffered rather than written
+    /// immediately: a blank node referenced exactly once can only be struck from the output
+    /// and inlined into its referrer once every one of its own statements is known, which
+    /// happens when [`finish`](Self::finish) flushes the buffer.
     pub fn format(&mut self, triple: &AsRefTriple<A>) -> Result<(), io::Error> {
+        if self.config.bnode_contract {
+            self.pending.push(AsRefTriple {
+                subject: triple.subject.clone(),
+                predicate: triple.predicate.clone(),
+                object: triple.object.clone(),
+            });
+            return Ok(());
+        }
+        self.format_streaming(triple)
+    }
+
+    fn format_streaming(&mut self, triple: &AsRefTriple<A>) -> Result<(), io::Error> {
         let last_subject = self.current_subject[..].last();
 
         if last_subject != Some(&triple.subject) {
@@ -263,11 +392,12 @@ where A: AsRef<str> + Clone + std::fmt::Debug + PartialEq,
 
                 match triple.subject {
                     AsRefNamedOrBlankNode::NamedNode(ref n) => {
-                        open.push_attribute(("rdf:about", n.iri.as_ref()))
+                        open.push_attribute(("rdf:about", self.relativize_iri(n.iri.as_ref())))
                     }
                     AsRefNamedOrBlankNode::BlankNode(ref n) => {
                         open.push_attribute(("rdf:nodeID", n.id.as_ref()))
                     }
+                    AsRefNamedOrBlankNode::Triple(_) => return unsupported_quoted_triple(),
                 }
                 self.write_start(Event::Start(open))
                     .map_err(map_err)?;
@@ -289,11 +419,12 @@ where A: AsRef<str> + Clone + std::fmt::Debug + PartialEq,
             let mut description_open = BytesStart::borrowed_name(b"rdf:Description");
             match triple.subject {
                 AsRefNamedOrBlankNode::NamedNode(ref n) => {
-                    description_open.push_attribute(("rdf:about", n.iri.as_ref()))
+                    description_open.push_attribute(("rdf:about", self.relativize_iri(n.iri.as_ref())))
                 }
                 AsRefNamedOrBlankNode::BlankNode(ref n) => {
                     description_open.push_attribute(("rdf:nodeID", n.id.as_ref()))
                 }
+                AsRefNamedOrBlankNode::Triple(_) => return unsupported_quoted_triple(),
             }
             self.write_start(Event::Start(description_open))
                 .map_err(map_err)?;
@@ -301,7 +432,7 @@ where A: AsRef<str> + Clone + std::fmt::Debug + PartialEq,
 
         let content = match &triple.object {
             AsRefTerm::NamedNode(n) => {
-                property_open.push_attribute(("rdf:resource", n.iri.as_ref()));
+                property_open.push_attribute(("rdf:resource", self.relativize_iri(n.iri.as_ref())));
                 None
             }
             AsRefTerm::BlankNode(n) => {
@@ -315,10 +446,11 @@ where A: AsRef<str> + Clone + std::fmt::Debug + PartialEq,
                     Some(value)
                 }
                 AsRefLiteral::Typed { value, datatype } => {
-                    property_open.push_attribute(("rdf:datatype", datatype.iri.as_ref()));
+                    property_open.push_attribute(("rdf:datatype", self.relativize_iri(datatype.iri.as_ref())));
                     Some(value)
                 }
             },
+            AsRefTerm::Triple(_) => return unsupported_quoted_triple(),
         };
         if let Some(content) = content {
             self.write_start(Event::Start(property_open))
@@ -336,6 +468,10 @@ where A: AsRef<str> + Clone + std::fmt::Debug + PartialEq,
 
     /// Finishes writing and returns the underlying `Write`
     pub fn finish(mut self) -> Result<W, io::Error> {
+        if self.config.bnode_contract {
+            self.flush_pending()?;
+        }
+
         while !self.current_close.is_empty() {
             self.write_close()?;
         }
@@ -344,9 +480,289 @@ where A: AsRef<str> + Clone + std::fmt::Debug + PartialEq,
             .map_err(map_err)?;
         Ok(self.writer.into_inner())
     }
+
+    /// Renders every buffered triple, striping blank nodes that are referenced exactly once
+    /// into a nested property element instead of a `rdf:nodeID` pointer, and abbreviating
+    /// well-formed `rdf:first`/`rdf:rest`/`rdf:nil` chains with `rdf:parseType="Collection"`.
+    fn flush_pending(&mut self) -> Result<(), io::Error> {
+        let mut order: Vec<AsRefNamedOrBlankNode<A>> = Vec::new();
+        let mut by_subject: HashMap<AsRefNamedOrBlankNode<A>, Vec<(AsRefNamedNode<A>, AsRefTerm<A>)>> =
+            HashMap::new();
+        let mut bnode_refcount: HashMap<AsRefBlankNode<A>, usize> = HashMap::new();
+
+        for triple in self.pending.drain(..) {
+            if !by_subject.contains_key(&triple.subject) {
+                order.push(triple.subject.clone());
+            }
+            if let AsRefTerm::BlankNode(bn) = &triple.object {
+                *bnode_refcount.entry(bn.clone()).or_insert(0) += 1;
+            }
+            by_subject
+                .entry(triple.subject.clone())
+                .or_insert_with(Vec::new)
+                .push((triple.predicate, triple.object));
+        }
+
+        let mut emitted: HashSet<AsRefBlankNode<A>> = HashSet::new();
+
+        // First pass: genuine roots, i.e. named nodes and blank nodes that are not
+        // referenced exactly once (unreferenced, or shared between several statements).
+        for subject in &order {
+            if let AsRefNamedOrBlankNode::BlankNode(bn) = subject {
+                if bnode_refcount.get(bn).copied().unwrap_or(0) == 1 {
+                    continue;
+                }
+            }
+            self.write_subject_tree(subject, &by_subject, &bnode_refcount, &mut emitted, &mut Vec::new())?;
+        }
+
+        // Second pass: blank nodes that looked like they'd be inlined but whose sole
+        // referrer never got around to writing them (a referrer inside a cycle, or a
+        // referrer that is itself never emitted). These are flushed as top-level nodes.
+        for subject in &order {
+            if let AsRefNamedOrBlankNode::BlankNode(bn) = subject {
+                if !emitted.contains(bn) {
+                    self.write_subject_tree(subject, &by_subject, &bnode_refcount, &mut emitted, &mut Vec::new())?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_subject_tree(
+        &mut self,
+        subject: &AsRefNamedOrBlankNode<A>,
+        by_subject: &HashMap<AsRefNamedOrBlankNode<A>, Vec<(AsRefNamedNode<A>, AsRefTerm<A>)>>,
+        bnode_refcount: &HashMap<AsRefBlankNode<A>, usize>,
+        emitted: &mut HashSet<AsRefBlankNode<A>>,
+        rendering: &mut Vec<AsRefBlankNode<A>>,
+    ) -> Result<(), io::Error> {
+        if let AsRefNamedOrBlankNode::BlankNode(bn) = subject {
+            emitted.insert(bn.clone());
+            rendering.push(bn.clone());
+        }
+
+        let properties = by_subject.get(subject).cloned().unwrap_or_default();
+        let rdf_type = properties.iter().find_map(|(p, o)| match (p, o) {
+            (p, AsRefTerm::NamedNode(nn)) if is_rdf_type(p) => Some(nn.clone()),
+            _ => None,
+        });
+
+        let mut open = if self.config.typed_node {
+            match &rdf_type {
+                Some(nn) => self.bytes_for_iri(&nn.iri),
+                None => BytesStart::borrowed_name(b"rdf:Description"),
+            }
+        } else {
+            BytesStart::borrowed_name(b"rdf:Description")
+        };
+        match subject {
+            AsRefNamedOrBlankNode::NamedNode(n) => open.push_attribute(("rdf:about", self.relativize_iri(n.iri.as_ref()))),
+            AsRefNamedOrBlankNode::BlankNode(n) => open.push_attribute(("rdf:nodeID", n.id.as_ref())),
+            AsRefNamedOrBlankNode::Triple(_) => return unsupported_quoted_triple(),
+        }
+        self.write_start(Event::Start(open)).map_err(map_err)?;
+
+        let mut consumed_type = false;
+        for (predicate, object) in &properties {
+            if self.config.typed_node
+                && rdf_type.is_some()
+                && !consumed_type
+                && is_rdf_type(predicate)
+                && matches!(object, AsRefTerm::NamedNode(_))
+            {
+                // Already expressed as the element name above.
+                consumed_type = true;
+                continue;
+            }
+            self.write_property(predicate, object, by_subject, bnode_refcount, emitted, rendering)?;
+        }
+
+        if let AsRefNamedOrBlankNode::BlankNode(_) = subject {
+            rendering.pop();
+        }
+
+        self.write_close()
+    }
+
+    fn write_property(
+        &mut self,
+        predicate: &AsRefNamedNode<A>,
+        object: &AsRefTerm<A>,
+        by_subject: &HashMap<AsRefNamedOrBlankNode<A>, Vec<(AsRefNamedNode<A>, AsRefTerm<A>)>>,
+        bnode_refcount: &HashMap<AsRefBlankNode<A>, usize>,
+        emitted: &mut HashSet<AsRefBlankNode<A>>,
+        rendering: &mut Vec<AsRefBlankNode<A>>,
+    ) -> Result<(), io::Error> {
+        // Only a blank node referenced exactly once can be safely struck from the top-level
+        // output (collection or plain striping): a node with any other refcount must keep
+        // being addressable as `rdf:nodeID` wherever else it's referenced, so it's left for
+        // `flush_pending`'s own passes to write out in full exactly once.
+        if let AsRefTerm::BlankNode(bn) = object {
+            if bnode_refcount.get(bn).copied().unwrap_or(0) == 1 && !rendering.contains(bn) {
+                if let Some((items, cells)) = collection_starting_at(bn, by_subject, bnode_refcount) {
+                    let mut property_open = self.bytes_for_iri(&predicate.iri);
+                    property_open.push_attribute(("rdf:parseType", "Collection"));
+                    self.write_start(Event::Start(property_open)).map_err(map_err)?;
+                    // The `rdf:first`/`rdf:rest` cell nodes themselves are fully consumed by
+                    // the collection syntax, so they must never also be flushed as top-level
+                    // nodes.
+                    emitted.extend(cells);
+                    for item in &items {
+                        match item {
+                            // A named node's own properties are its own top-level statements
+                            // (written once by `flush_pending`'s root pass); referencing it
+                            // here must not also repeat them, so only a bare pointer is
+                            // written, the same way `rdf:resource` would elsewhere.
+                            AsRefNamedOrBlankNode::NamedNode(n) => {
+                                let mut item_open = BytesStart::borrowed_name(b"rdf:Description");
+                                item_open.push_attribute(("rdf:about", self.relativize_iri(n.iri.as_ref())));
+                                self.write_event(Event::Empty(item_open)).map_err(map_err)?;
+                            }
+                            // A blank node item follows the same single-reference rule as any
+                            // other blank node: inline it in full only if this is its one
+                            // reference, otherwise just point at it.
+                            AsRefNamedOrBlankNode::BlankNode(item_bn) => {
+                                if bnode_refcount.get(item_bn).copied().unwrap_or(0) == 1
+                                    && !rendering.contains(item_bn)
+                                {
+                                    emitted.insert(item_bn.clone());
+                                    self.write_subject_tree(item, by_subject, bnode_refcount, emitted, rendering)?;
+                                } else {
+                                    let mut item_open = BytesStart::borrowed_name(b"rdf:Description");
+                                    item_open.push_attribute(("rdf:nodeID", item_bn.id.as_ref()));
+                                    self.write_event(Event::Empty(item_open)).map_err(map_err)?;
+                                }
+                            }
+                            AsRefNamedOrBlankNode::Triple(_) => return unsupported_quoted_triple(),
+                        }
+                    }
+                    return self.write_close();
+                }
+
+                let property_open = self.bytes_for_iri(&predicate.iri);
+                self.write_start(Event::Start(property_open)).map_err(map_err)?;
+                self.write_subject_tree(
+                    &AsRefNamedOrBlankNode::BlankNode(bn.clone()),
+                    by_subject,
+                    bnode_refcount,
+                    emitted,
+                    rendering,
+                )?;
+                return self.write_close();
+            }
+        }
+
+        let mut property_open = self.bytes_for_iri(&predicate.iri);
+        let content = match object {
+            AsRefTerm::NamedNode(n) => {
+                property_open.push_attribute(("rdf:resource", self.relativize_iri(n.iri.as_ref())));
+                None
+            }
+            AsRefTerm::BlankNode(n) => {
+                property_open.push_attribute(("rdf:nodeID", n.id.as_ref()));
+                None
+            }
+            AsRefTerm::Literal(l) => match l {
+                AsRefLiteral::Simple { value } => Some(value),
+                AsRefLiteral::LanguageTaggedString { value, language } => {
+                    property_open.push_attribute(("xml:lang", language.as_ref()));
+                    Some(value)
+                }
+                AsRefLiteral::Typed { value, datatype } => {
+                    property_open.push_attribute(("rdf:datatype", self.relativize_iri(datatype.iri.as_ref())));
+                    Some(value)
+                }
+            },
+            AsRefTerm::Triple(_) => return unsupported_quoted_triple(),
+        };
+        if let Some(content) = content {
+            self.write_start(Event::Start(property_open)).map_err(map_err)?;
+            self.write_event(Event::Text(BytesText::from_plain_str(&content.as_ref())))
+                .map_err(map_err)?;
+            self.write_close()?;
+        } else {
+            self.write_event(Event::Empty(property_open)).map_err(map_err)?;
+        }
+        Ok(())
+    }
+}
+
+fn is_rdf_type<A: AsRef<str>>(predicate: &AsRefNamedNode<A>) -> bool {
+    predicate.iri.as_ref() == "http://www.w3.org/1999/02/22-rdf-syntax-ns#type"
+}
+
+fn is_rdf_first<A: AsRef<str>>(predicate: &AsRefNamedNode<A>) -> bool {
+    predicate.iri.as_ref() == "http://www.w3.org/1999/02/22-rdf-syntax-ns#first"
+}
+
+fn is_rdf_rest<A: AsRef<str>>(predicate: &AsRefNamedNode<A>) -> bool {
+    predicate.iri.as_ref() == "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest"
+}
+
+fn is_rdf_nil(iri: &str) -> bool {
+    iri == "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil"
+}
+
+/// If `bn` is the head of a well-formed `rdf:first`/`rdf:rest` chain ending in `rdf:nil`,
+/// returns its items in order. A node only counts as a collection cell if it has exactly the
+/// two list properties (nothing else hangs off it), so a list node that's also been given
+/// extra properties is left as an ordinary blank node instead of being silently dropped. Every
+/// cell, not just the head, must also be referenced exactly once: a cell referenced elsewhere
+/// too can't be struck from the output, since the collection syntax consumes it without
+/// leaving it addressable for that other reference.
+///
+/// A chain that cycles back into one of its own cells is not a collection either: bail out as
+/// soon as `cursor` revisits a cell already seen, rather than following it forever.
+fn collection_starting_at<A>(
+    bn: &AsRefBlankNode<A>,
+    by_subject: &HashMap<AsRefNamedOrBlankNode<A>, Vec<(AsRefNamedNode<A>, AsRefTerm<A>)>>,
+    bnode_refcount: &HashMap<AsRefBlankNode<A>, usize>,
+) -> Option<(Vec<AsRefNamedOrBlankNode<A>>, Vec<AsRefBlankNode<A>>)>
+where
+    A: AsRef<str> + Clone + PartialEq + Eq + std::hash::Hash,
+{
+    let mut items = Vec::new();
+    let mut cells = Vec::new();
+    let mut cursor = bn.clone();
+    loop {
+        if cells.contains(&cursor) {
+            return None;
+        }
+        if bnode_refcount.get(&cursor).copied().unwrap_or(0) != 1 {
+            return None;
+        }
+        let properties = by_subject.get(&AsRefNamedOrBlankNode::BlankNode(cursor.clone()))?;
+        if properties.len() != 2 {
+            return None;
+        }
+        cells.push(cursor.clone());
+        let first = properties.iter().find(|(p, _)| is_rdf_first(p))?;
+        let rest = properties.iter().find(|(p, _)| is_rdf_rest(p))?;
+        items.push(match &first.1 {
+            AsRefTerm::NamedNode(n) => AsRefNamedOrBlankNode::NamedNode(n.clone()),
+            AsRefTerm::BlankNode(n) => AsRefNamedOrBlankNode::BlankNode(n.clone()),
+            AsRefTerm::Literal(_) | AsRefTerm::Triple(_) => return None,
+        });
+        match &rest.1 {
+            AsRefTerm::NamedNode(n) if is_rdf_nil(n.iri.as_ref()) => return Some((items, cells)),
+            AsRefTerm::BlankNode(n) => cursor = n.clone(),
+            _ => return None,
+        }
+    }
 }
 
 
+/// There's no standard way to embed an RDF-star quoted triple in RDF/XML, so this declines
+/// rather than emit something that would silently drop the nesting.
+fn unsupported_quoted_triple() -> Result<(), io::Error> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "AbbrevRdfXmlFormatter cannot serialize an RDF-star quoted triple: RDF/XML has no syntax for it",
+    ))
+}
+
 fn map_err(error: quick_xml::Error) -> io::Error {
     if let quick_xml::Error::Io(error) = error {
         error
@@ -370,4 +786,416 @@ fn split_iri<A:AsRef<str>>(iri: &A) -> (&str, &str) {
     } else {
         (iri, "")
     }
+}
+
+/// Async (tokio) counterpart of [`AbbrevRdfXmlFormatter`], gated behind the `async-tokio`
+/// feature so synchronous users don't pull in tokio at all.
+///
+/// This checkout has no `Cargo.toml` for the `xml` crate either, so — same caveat as
+/// [`rio_turtle::ntriples::async_io`](../../rio_turtle/ntriples/async_io/index.html) — the
+/// `async-tokio` feature and optional `tokio` dependency need to be added to the manifest
+/// before this module can build:
+/// ```toml
+/// [features]
+/// async-tokio = ["dep:tokio"]
+///
+/// [dependencies]
+/// tokio = { version = "1", features = ["io-util"], optional = true }
+/// ```
+#[cfg(feature = "async-tokio")]
+pub mod async_io {
+    use super::*;
+    use std::{cell::RefCell, rc::Rc};
+    use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+    /// A `Write` implementation that just appends to a shared, in-memory buffer.
+    ///
+    /// `quick_xml`'s `Writer` only knows how to write synchronously, so every formatted event
+    /// lands here first; [`AsyncAbbrevRdfXmlFormatter`] is what drains this buffer into the
+    /// real `AsyncWrite` sink between calls.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// An [`AbbrevRdfXmlFormatter`] that flushes into a `tokio::io::AsyncWrite` instead of a
+    /// blocking `std::io::Write`.
+    pub struct AsyncAbbrevRdfXmlFormatter<A: AsRef<str>, W> {
+        inner: AbbrevRdfXmlFormatter<A, SharedBuffer>,
+        buffer: SharedBuffer,
+        write: W,
+    }
+
+    impl<A, W> AsyncAbbrevRdfXmlFormatter<A, W>
+    where
+        A: AsRef<str> + Clone + std::fmt::Debug + PartialEq + Eq + std::hash::Hash,
+        W: AsyncWrite + Unpin,
+    {
+        /// Builds a new formatter and writes the RDF/XML declaration and root element.
+        pub async fn new(write: W, config: AbbrevRdfXmlFormatterConfig) -> Result<Self, io::Error> {
+            let buffer = SharedBuffer::default();
+            let inner = AbbrevRdfXmlFormatter::new(buffer.clone(), config)?;
+            let mut formatter = Self { inner, buffer, write };
+            formatter.flush_buffer().await?;
+            Ok(formatter)
+        }
+
+        pub async fn format(&mut self, triple: &AsRefTriple<A>) -> Result<(), io::Error> {
+            self.inner.format(triple)?;
+            self.flush_buffer().await
+        }
+
+        pub async fn format_quad(&mut self, quad: &AsRefQuad<A>) -> Result<(), io::Error> {
+            self.inner.format_quad(quad)?;
+            self.flush_buffer().await
+        }
+
+        /// Finishes writing, flushes the sink, and returns it.
+        pub async fn finish(mut self) -> Result<W, io::Error> {
+            let buffer = self.inner.finish()?;
+            let pending = std::mem::take(&mut *buffer.0.borrow_mut());
+            if !pending.is_empty() {
+                self.write.write_all(&pending).await?;
+            }
+            self.write.flush().await?;
+            Ok(self.write)
+        }
+
+        async fn flush_buffer(&mut self) -> Result<(), io::Error> {
+            let pending = std::mem::take(&mut *self.buffer.0.borrow_mut());
+            if !pending.is_empty() {
+                self.write.write_all(&pending).await?;
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nn(iri: &str) -> AsRefNamedNode<String> {
+        AsRefNamedNode { iri: iri.to_string() }
+    }
+
+    fn bn(id: &str) -> AsRefBlankNode<String> {
+        AsRefBlankNode { id: id.to_string() }
+    }
+
+    fn lit(value: &str) -> AsRefTerm<String> {
+        AsRefTerm::Literal(AsRefLiteral::Simple { value: value.to_string() })
+    }
+
+    fn triple(
+        subject: AsRefNamedOrBlankNode<String>,
+        predicate: AsRefNamedNode<String>,
+        object: AsRefTerm<String>,
+    ) -> AsRefTriple<String> {
+        AsRefTriple { subject, predicate, object }
+    }
+
+    fn format_contracted(triples: Vec<AsRefTriple<String>>) -> String {
+        let mut config = AbbrevRdfXmlFormatterConfig::new();
+        config.bnode_contract = true;
+        let mut formatter = AbbrevRdfXmlFormatter::new(Vec::new(), config).unwrap();
+        for t in &triples {
+            formatter.format(t).unwrap();
+        }
+        String::from_utf8(formatter.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn single_reference_blank_node_is_inlined_once() {
+        let out = format_contracted(vec![
+            triple(
+                AsRefNamedOrBlankNode::NamedNode(nn("http://example.com/s")),
+                nn("http://example.com/p"),
+                AsRefTerm::BlankNode(bn("o")),
+            ),
+            triple(
+                AsRefNamedOrBlankNode::BlankNode(bn("o")),
+                nn("http://example.com/q"),
+                lit("hello"),
+            ),
+        ]);
+        assert_eq!(out.matches("hello<").count(), 1);
+    }
+
+    /// Two literal-valued properties on the same subject must come out as siblings, not with
+    /// the second nested inside the first's still-open element.
+    #[test]
+    fn sibling_literal_properties_are_not_nested() {
+        let out = format_contracted(vec![
+            triple(
+                AsRefNamedOrBlankNode::NamedNode(nn("http://example.com/s")),
+                nn("http://example.com/p1"),
+                lit("a"),
+            ),
+            triple(
+                AsRefNamedOrBlankNode::NamedNode(nn("http://example.com/s")),
+                nn("http://example.com/p2"),
+                lit("b"),
+            ),
+        ]);
+        assert!(out.find("</p1>").unwrap() < out.find("<p2").unwrap());
+    }
+
+    /// Two unrelated top-level subjects must come out as siblings under `rdf:RDF`, not with
+    /// the second nested inside the first.
+    #[test]
+    fn sibling_top_level_subjects_are_not_nested() {
+        let out = format_contracted(vec![
+            triple(
+                AsRefNamedOrBlankNode::NamedNode(nn("http://example.com/s1")),
+                nn("http://example.com/p"),
+                lit("a"),
+            ),
+            triple(
+                AsRefNamedOrBlankNode::NamedNode(nn("http://example.com/s2")),
+                nn("http://example.com/p"),
+                lit("b"),
+            ),
+        ]);
+        assert!(
+            out.find("rdf:about=\"http://example.com/s1\"").unwrap()
+                < out.find("</rdf:Description>").unwrap()
+        );
+        assert!(
+            out.find("</rdf:Description>").unwrap()
+                < out.find("rdf:about=\"http://example.com/s2\"").unwrap()
+        );
+    }
+
+    fn quad(
+        subject: AsRefNamedOrBlankNode<String>,
+        predicate: AsRefNamedNode<String>,
+        object: AsRefTerm<String>,
+        graph_name: Option<AsRefNamedOrBlankNode<String>>,
+    ) -> AsRefQuad<String> {
+        AsRefQuad { subject, predicate, object, graph_name }
+    }
+
+    /// `format_quad` groups by graph the same way `format` groups by subject: a subject
+    /// repeated in a different graph gets its own `rdf:Description` rather than being folded
+    /// into the block already open for the earlier graph.
+    #[test]
+    fn format_quad_groups_by_graph() {
+        let mut formatter =
+            AbbrevRdfXmlFormatter::new(Vec::new(), AbbrevRdfXmlFormatterConfig::new()).unwrap();
+        formatter
+            .format_quad(&quad(
+                AsRefNamedOrBlankNode::NamedNode(nn("http://example.com/s")),
+                nn("http://example.com/p"),
+                lit("default"),
+                None,
+            ))
+            .unwrap();
+        formatter
+            .format_quad(&quad(
+                AsRefNamedOrBlankNode::NamedNode(nn("http://example.com/s")),
+                nn("http://example.com/p"),
+                lit("named"),
+                Some(AsRefNamedOrBlankNode::NamedNode(nn("http://example.com/g"))),
+            ))
+            .unwrap();
+        let out = String::from_utf8(formatter.finish().unwrap()).unwrap();
+
+        // Same subject, two different graphs: two separate `rdf:Description` blocks, not one
+        // merged block with both properties.
+        assert_eq!(out.matches("rdf:Description").count(), 4);
+        assert_eq!(out.matches("default<").count(), 1);
+        assert_eq!(out.matches("named<").count(), 1);
+    }
+
+    /// A blank node list head referenced by two different subjects can't be safely struck
+    /// from the output at either referrer (only one of them could keep it addressable), so
+    /// neither referrer may collection-abbreviate or inline it; it's written out, in full,
+    /// exactly once as its own root.
+    #[test]
+    fn shared_list_head_is_not_duplicated() {
+        let list = AsRefNamedOrBlankNode::BlankNode(bn("list"));
+        let rest = AsRefNamedOrBlankNode::BlankNode(bn("rest"));
+        let rdf_first = nn("http://www.w3.org/1999/02/22-rdf-syntax-ns#first");
+        let rdf_rest = nn("http://www.w3.org/1999/02/22-rdf-syntax-ns#rest");
+        let rdf_nil = AsRefTerm::NamedNode(nn("http://www.w3.org/1999/02/22-rdf-syntax-ns#nil"));
+
+        let out = format_contracted(vec![
+            triple(
+                AsRefNamedOrBlankNode::NamedNode(nn("http://example.com/s1")),
+                nn("http://example.com/p"),
+                AsRefTerm::BlankNode(bn("list")),
+            ),
+            triple(
+                AsRefNamedOrBlankNode::NamedNode(nn("http://example.com/s2")),
+                nn("http://example.com/p"),
+                AsRefTerm::BlankNode(bn("list")),
+            ),
+            triple(list.clone(), rdf_first.clone(), lit("a")),
+            triple(list.clone(), rdf_rest.clone(), AsRefTerm::BlankNode(bn("rest"))),
+            triple(rest.clone(), rdf_first, lit("b")),
+            triple(rest, rdf_rest, rdf_nil),
+        ]);
+
+        // Both referrers point at it, plus its own root `rdf:Description`.
+        assert_eq!(out.matches("rdf:nodeID=\"list\"").count(), 3);
+        // Its tail is referenced exactly once (by `list` itself), so it's inlined and never
+        // separately addressed.
+        assert_eq!(out.matches("rdf:nodeID=\"rest\"").count(), 1);
+        // The list's own content is written exactly once, regardless of how many referrers
+        // point at the head.
+        assert_eq!(out.matches("a<").count(), 1);
+        assert_eq!(out.matches("b<").count(), 1);
+    }
+
+    /// A two-node cycle can't be fully inlined (something has to stay addressable to break
+    /// the cycle), but it must still terminate and each node must be written exactly once.
+    #[test]
+    fn two_node_cycle_terminates_without_duplication() {
+        let out = format_contracted(vec![
+            triple(
+                AsRefNamedOrBlankNode::BlankNode(bn("a")),
+                nn("http://example.com/p"),
+                AsRefTerm::BlankNode(bn("b")),
+            ),
+            triple(
+                AsRefNamedOrBlankNode::BlankNode(bn("b")),
+                nn("http://example.com/p"),
+                AsRefTerm::BlankNode(bn("a")),
+            ),
+        ]);
+
+        // `a`'s own root open plus the back-reference that closes the cycle.
+        assert_eq!(out.matches("rdf:nodeID=\"a\"").count(), 2);
+        // `b` is only ever inlined into `a`, never referenced again.
+        assert_eq!(out.matches("rdf:nodeID=\"b\"").count(), 1);
+    }
+
+    /// `collection_starting_at` follows `rdf:rest` pointers on the caller's behalf, so a cell
+    /// that cycles back into an earlier one (well-typed, just non-canonical RDF) must be
+    /// rejected rather than followed forever; the cells fall back to ordinary striped blank
+    /// nodes instead of a `rdf:parseType="Collection"`.
+    #[test]
+    fn cyclic_rest_chain_does_not_abbreviate_as_a_collection() {
+        let cell_a = AsRefNamedOrBlankNode::BlankNode(bn("cell_a"));
+        let cell_b = AsRefNamedOrBlankNode::BlankNode(bn("cell_b"));
+        let rdf_first = nn("http://www.w3.org/1999/02/22-rdf-syntax-ns#first");
+        let rdf_rest = nn("http://www.w3.org/1999/02/22-rdf-syntax-ns#rest");
+
+        let out = format_contracted(vec![
+            triple(
+                AsRefNamedOrBlankNode::NamedNode(nn("http://example.com/s")),
+                nn("http://example.com/p"),
+                AsRefTerm::BlankNode(bn("cell_a")),
+            ),
+            triple(cell_a.clone(), rdf_first.clone(), AsRefTerm::NamedNode(nn("http://example.com/item_a"))),
+            triple(cell_a, rdf_rest.clone(), AsRefTerm::BlankNode(bn("cell_b"))),
+            triple(cell_b.clone(), rdf_first, AsRefTerm::NamedNode(nn("http://example.com/item_b"))),
+            triple(cell_b, rdf_rest, AsRefTerm::BlankNode(bn("cell_a"))),
+        ]);
+
+        assert!(!out.contains("rdf:parseType"));
+    }
+
+    /// A collection cell referenced from outside the chain (not just by the previous cell's
+    /// `rdf:rest`) can't be safely consumed by `rdf:parseType="Collection"`: doing so would
+    /// also have to keep it addressable for the external reference, producing two disjoint
+    /// copies of the same blank node. The whole chain must fall back to plain striping instead.
+    #[test]
+    fn collection_with_externally_referenced_interior_cell_is_not_abbreviated() {
+        let list = AsRefNamedOrBlankNode::BlankNode(bn("list"));
+        let tail = AsRefNamedOrBlankNode::BlankNode(bn("tail"));
+        let rdf_first = nn("http://www.w3.org/1999/02/22-rdf-syntax-ns#first");
+        let rdf_rest = nn("http://www.w3.org/1999/02/22-rdf-syntax-ns#rest");
+        let rdf_nil = AsRefTerm::NamedNode(nn("http://www.w3.org/1999/02/22-rdf-syntax-ns#nil"));
+
+        let out = format_contracted(vec![
+            triple(
+                AsRefNamedOrBlankNode::NamedNode(nn("http://example.com/s")),
+                nn("http://example.com/p"),
+                AsRefTerm::BlankNode(bn("list")),
+            ),
+            triple(
+                list.clone(),
+                rdf_first.clone(),
+                AsRefTerm::NamedNode(nn("http://example.com/item_a")),
+            ),
+            triple(list, rdf_rest.clone(), AsRefTerm::BlankNode(bn("tail"))),
+            triple(
+                tail.clone(),
+                rdf_first,
+                AsRefTerm::NamedNode(nn("http://example.com/item_b")),
+            ),
+            triple(tail, rdf_rest, rdf_nil),
+            // An external statement about the tail cell, independent of the list structure.
+            triple(
+                AsRefNamedOrBlankNode::NamedNode(nn("http://example.com/external")),
+                nn("http://example.com/marker"),
+                AsRefTerm::BlankNode(bn("tail")),
+            ),
+        ]);
+
+        assert!(!out.contains("rdf:parseType"));
+        // Both referrers (the list's own `rdf:rest` and the external statement) point at it,
+        // plus its own root `rdf:Description`; it's never written out a second time with its
+        // `rdf:first`/`rdf:rest` properties duplicated.
+        assert_eq!(out.matches("rdf:nodeID=\"tail\"").count(), 3);
+    }
+
+    #[test]
+    fn quoted_triple_subject_is_reported_as_unsupported() {
+        let mut formatter =
+            AbbrevRdfXmlFormatter::new(Vec::new(), AbbrevRdfXmlFormatterConfig::new()).unwrap();
+        let quoted = AsRefQuotedTriple {
+            subject: AsRefNamedOrBlankNode::NamedNode(nn("http://example.com/s")),
+            predicate: nn("http://example.com/p"),
+            object: lit("o"),
+        };
+        let err = formatter
+            .format(&triple(
+                AsRefNamedOrBlankNode::Triple(Box::new(quoted)),
+                nn("http://example.com/p2"),
+                lit("o2"),
+            ))
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn xml_base_relativizes_matching_iris_only() {
+        let mut config = AbbrevRdfXmlFormatterConfig::new();
+        config.base = Some("http://example.com".to_string());
+        let formatter: AbbrevRdfXmlFormatter<String, Vec<u8>> =
+            AbbrevRdfXmlFormatter::new(Vec::new(), config).unwrap();
+        assert_eq!(formatter.relativize_iri("http://example.com/foo"), "/foo");
+        assert_eq!(formatter.relativize_iri("http://example.com#frag"), "#frag");
+        assert_eq!(formatter.relativize_iri("http://other.com/foo"), "http://other.com/foo");
+    }
+
+    /// A base already ending in `/` or `#` (the common way to declare a vocabulary base) must
+    /// relativize every IRI it prefixes, not just ones that happen to continue with another
+    /// separator.
+    #[test]
+    fn xml_base_relativizes_against_trailing_separator() {
+        let mut config = AbbrevRdfXmlFormatterConfig::new();
+        config.base = Some("http://xmlns.com/foaf/0.1/".to_string());
+        let formatter: AbbrevRdfXmlFormatter<String, Vec<u8>> =
+            AbbrevRdfXmlFormatter::new(Vec::new(), config).unwrap();
+        assert_eq!(formatter.relativize_iri("http://xmlns.com/foaf/0.1/Person"), "Person");
+
+        let mut config = AbbrevRdfXmlFormatterConfig::new();
+        config.base = Some("http://example.com/onto#".to_string());
+        let formatter: AbbrevRdfXmlFormatter<String, Vec<u8>> =
+            AbbrevRdfXmlFormatter::new(Vec::new(), config).unwrap();
+        assert_eq!(formatter.relativize_iri("http://example.com/onto#Foo"), "Foo");
+    }
 }
\ No newline at end of file